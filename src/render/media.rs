@@ -1,7 +1,8 @@
 use crate::render::properties::WindowSize;
-use crossterm::cursor;
-use image::{DynamicImage, ImageError};
-use std::{fmt::Debug, fs, io, rc::Rc};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use crossterm::{cursor, execute};
+use image::{imageops::FilterType, DynamicImage, ImageError};
+use std::{env, fmt::Debug, fs, io, io::Write, rc::Rc};
 use viuer::ViuError;
 
 /// An image.
@@ -25,10 +26,93 @@ impl Image {
     }
 }
 
+/// The medium used to transmit an image to a Kitty-compatible terminal.
+///
+/// `viuer`'s Kitty support writes the image to a temp file and asks the terminal to read it from
+/// disk, which only works when `presenterm` and the terminal share a filesystem. `Chunked` avoids
+/// that requirement entirely, which is what makes rendering work over SSH.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KittyMedium {
+    /// Write the image to a temp file and let the terminal read it from disk.
+    TempFile,
+
+    /// Stream the image inline as base64-encoded chunks of the Kitty graphics escape sequence.
+    #[default]
+    Chunked,
+}
+
+/// The terminal image protocol used to render an image.
+///
+/// `Auto` picks the best protocol the current terminal seems to support, falling back all the way
+/// down to `AsciiBlocks` when nothing better is detected. Any other variant forces that protocol
+/// regardless of what's detected, which is what a `--image-protocol`-style CLI/config override
+/// should set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImageProtocol {
+    /// Detect the best protocol supported by the current terminal.
+    #[default]
+    Auto,
+
+    /// The Kitty graphics protocol.
+    Kitty,
+
+    /// The iTerm2 inline images protocol.
+    Iterm2,
+
+    /// The DEC Sixel graphics protocol.
+    Sixel,
+
+    /// A Unicode halfblock-based ASCII approximation, supported virtually everywhere.
+    AsciiBlocks,
+}
+
+// A concrete image protocol to render with, i.e. `ImageProtocol` with `Auto` already resolved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ResolvedProtocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+    AsciiBlocks,
+}
+
+/// The maximum size, in bytes, of a single base64-encoded chunk sent in a Kitty graphics escape
+/// sequence, as mandated by the protocol.
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// A rectangular region of terminal cells, e.g. the area an image was drawn into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rect {
+    /// The column this rectangle starts at.
+    pub start_column: u16,
+
+    /// The row this rectangle starts at.
+    pub start_row: u16,
+
+    /// The width, in columns.
+    pub width: u16,
+
+    /// The height, in rows.
+    pub height: u16,
+}
+
 /// A media render.
-pub struct MediaRender;
+pub struct MediaRender {
+    protocol: ImageProtocol,
+    kitty_medium: KittyMedium,
+}
+
+impl Default for MediaRender {
+    fn default() -> Self {
+        Self::new(ImageProtocol::default(), KittyMedium::default())
+    }
+}
 
 impl MediaRender {
+    /// Construct a new media render that uses the given protocol and Kitty transmission medium.
+    pub fn new(protocol: ImageProtocol, kitty_medium: KittyMedium) -> Self {
+        Self { protocol, kitty_medium }
+    }
+
     /// Draw an image.
     ///
     /// This will use the current terminal size and try to render the image where the cursor is
@@ -37,40 +121,197 @@ impl MediaRender {
     ///
     /// In case the image does not fit, it will be resized to fit the screen, preserving the aspect
     /// ratio.
-    pub fn draw_image(&self, image: &Image, dimensions: &WindowSize) -> Result<(), RenderImageError> {
+    ///
+    /// Returns the cell-space rectangle the image was drawn into, so callers can avoid redrawing
+    /// an unchanged image, reserve its area in a layout, or interleave other content around it.
+    pub fn draw_image(&self, image: &Image, dimensions: &WindowSize) -> Result<Rect, RenderImageError> {
         let position = cursor::position()?;
         let image = &image.0;
 
+        let rect = Self::compute_rect(image.width(), image.height(), dimensions, position.1);
+        let width_in_columns = rect.width as u32;
+        let width_px = (width_in_columns as f64 * dimensions.pixels_per_column()) as u32;
+        let height_px = (rect.height as u32 as f64 * dimensions.pixels_per_row()) as u32;
+
+        let protocol = self.resolve_protocol();
+        match protocol {
+            ResolvedProtocol::Kitty if self.kitty_medium == KittyMedium::Chunked => {
+                self.draw_image_kitty_chunked(image, rect.start_column, rect.start_row, width_px, height_px)?;
+            }
+            ResolvedProtocol::Sixel => {
+                self.draw_image_sixel(image, rect.start_column, rect.start_row, width_px, height_px)?;
+            }
+            ResolvedProtocol::Kitty | ResolvedProtocol::Iterm2 | ResolvedProtocol::AsciiBlocks => {
+                let config = viuer::Config {
+                    width: Some(width_in_columns),
+                    x: rect.start_column,
+                    y: rect.start_row as i16,
+                    use_kitty: protocol == ResolvedProtocol::Kitty,
+                    use_iterm: protocol == ResolvedProtocol::Iterm2,
+                    ..Default::default()
+                };
+                self.clear_viuer_temp_files();
+                viuer::print(image, &config)?;
+            }
+        }
+        Ok(rect)
+    }
+
+    // Compute the cell-space rectangle an image of the given pixel dimensions would be drawn
+    // into, centered horizontally and starting at `cursor_row`, shrinking it to fit the screen
+    // (preserving aspect ratio) if it doesn't fit as-is. This is a pure function specifically so
+    // the resize/shrink/truncation math can be unit tested without a real terminal cursor.
+    fn compute_rect(image_width: u32, image_height: u32, dimensions: &WindowSize, cursor_row: u16) -> Rect {
         // Compute the image's width in columns by translating pixels -> columns.
         let column_in_pixels = dimensions.pixels_per_column();
         let column_margin = (dimensions.columns as f64 * 0.95) as u32;
-        let mut width_in_columns = (image.width() as f64 / column_in_pixels) as u32;
+        let mut width_in_columns = (image_width as f64 / column_in_pixels) as u32;
 
         // Do the same for its height.
         let row_in_pixels = dimensions.pixels_per_row();
-        let height_in_rows = (image.height() as f64 / row_in_pixels) as u32;
+        let height_in_rows = (image_height as f64 / row_in_pixels) as u32;
 
         // If the image doesn't fit vertically, shrink it.
-        let available_height = dimensions.rows.saturating_sub(position.1) as u32;
-        if height_in_rows > available_height {
+        let available_height = dimensions.rows.saturating_sub(cursor_row) as u32;
+        let height_in_rows = if height_in_rows > available_height {
             // Because we only use the width to draw, here we scale the width based on how much we
             // need to shrink the height.
             let shrink_ratio = available_height as f64 / height_in_rows as f64;
             width_in_columns = (width_in_columns as f64 * shrink_ratio) as u32;
-        }
-        // Don't go too far wide.
+            available_height
+        } else {
+            height_in_rows
+        };
+        // Don't go too far wide, shrinking the height by the same ratio to preserve aspect ratio.
+        let height_in_rows = if width_in_columns > column_margin {
+            (height_in_rows as f64 * column_margin as f64 / width_in_columns as f64) as u32
+        } else {
+            height_in_rows
+        };
         let width_in_columns = width_in_columns.min(column_margin);
 
         // Draw it in the middle
         let start_column = dimensions.columns / 2 - (width_in_columns / 2) as u16;
-        let config = viuer::Config {
-            width: Some(width_in_columns),
-            x: start_column,
-            y: position.1 as i16,
-            ..Default::default()
+        Rect { start_column, start_row: cursor_row, width: width_in_columns as u16, height: height_in_rows as u16 }
+    }
+
+    // Resolve `self.protocol` into a concrete protocol, running detection if it's `Auto`. This is
+    // a separate, `Auto`-less enum so the dispatch below can be an exhaustive match without a
+    // dead `Auto` arm to maintain.
+    fn resolve_protocol(&self) -> ResolvedProtocol {
+        match self.protocol {
+            ImageProtocol::Auto => Self::detect_protocol(),
+            ImageProtocol::Kitty => ResolvedProtocol::Kitty,
+            ImageProtocol::Iterm2 => ResolvedProtocol::Iterm2,
+            ImageProtocol::Sixel => ResolvedProtocol::Sixel,
+            ImageProtocol::AsciiBlocks => ResolvedProtocol::AsciiBlocks,
+        }
+    }
+
+    // Detect the best image protocol the current terminal seems to support.
+    fn detect_protocol() -> ResolvedProtocol {
+        if Self::is_kitty() {
+            ResolvedProtocol::Kitty
+        } else if Self::is_iterm2() {
+            ResolvedProtocol::Iterm2
+        } else if Self::is_sixel_capable() {
+            ResolvedProtocol::Sixel
+        } else {
+            ResolvedProtocol::AsciiBlocks
+        }
+    }
+
+    // Whether we're running inside a Kitty-compatible terminal.
+    fn is_kitty() -> bool {
+        env::var("KITTY_WINDOW_ID").is_ok() || env::var("TERM").map(|term| term.contains("kitty")).unwrap_or(false)
+    }
+
+    // Whether we're running inside an iTerm2-compatible terminal.
+    fn is_iterm2() -> bool {
+        env::var("TERM_PROGRAM")
+            .map(|program| program == "iTerm.app" || program == "WezTerm")
+            .unwrap_or(false)
+    }
+
+    // Whether the terminal likely supports Sixel graphics. There's no reliable, portable way to
+    // query this without an in-band terminal round-trip, so this is a best-effort heuristic based
+    // on terminals known to support it; a forced `ImageProtocol::Sixel` override is always
+    // available for terminals this doesn't recognize.
+    fn is_sixel_capable() -> bool {
+        env::var("TERM")
+            .map(|term| term.contains("sixel") || term.contains("foot") || term.contains("mlterm"))
+            .unwrap_or(false)
+    }
+
+    // Transmit `image` to a Kitty-compatible terminal using the chunked transfer medium, which
+    // requires no shared filesystem between `presenterm` and the terminal and therefore works over
+    // SSH.
+    fn draw_image_kitty_chunked(
+        &self,
+        image: &DynamicImage,
+        start_column: u16,
+        row: u16,
+        width_px: u32,
+        height_px: u32,
+    ) -> Result<(), RenderImageError> {
+        // These are the dimensions we actually resize and transmit the image at, so the `s=`/`v=`
+        // keys below must be derived from them rather than from the original, possibly-zero ones.
+        let width_px = width_px.max(1);
+        let height_px = height_px.max(1);
+        let resized = image.resize_exact(width_px, height_px, FilterType::Lanczos3);
+        // Images without an alpha channel are sent as RGB (`f=24`) to save a quarter of the
+        // payload; only images whose pixel format has an alpha channel pay for it (`f=32`).
+        let (format, raw) = if resized.color().has_alpha() {
+            (32, resized.to_rgba8().into_raw())
+        } else {
+            (24, resized.to_rgb8().into_raw())
         };
-        self.clear_viuer_temp_files();
-        viuer::print(image, &config)?;
+        let encoded = STANDARD.encode(&raw);
+        let sequence = Self::build_kitty_chunks(format, width_px, height_px, &encoded);
+
+        let mut stdout = io::stdout();
+        execute!(stdout, cursor::MoveTo(start_column, row))?;
+        stdout.write_all(sequence.as_bytes())?;
+        stdout.flush()?;
+        Ok(())
+    }
+
+    // Split a base64-encoded image payload into Kitty graphics protocol escape sequences, each
+    // carrying at most `KITTY_CHUNK_SIZE` bytes of payload. The first chunk carries the control
+    // keys; every other chunk only carries `m=`, which is `1` except on the very last chunk.
+    fn build_kitty_chunks(format: u8, width_px: u32, height_px: u32, encoded: &str) -> String {
+        let mut out = String::new();
+        let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+        let last_index = chunks.len().saturating_sub(1);
+        for (index, chunk) in chunks.iter().enumerate() {
+            let more = if index == last_index { 0 } else { 1 };
+            if index == 0 {
+                out.push_str(&format!("\x1b_Ga=T,f={format},s={width_px},v={height_px},m={more};"));
+            } else {
+                out.push_str(&format!("\x1b_Gm={more};"));
+            }
+            out.push_str(std::str::from_utf8(chunk).expect("base64 output is ASCII"));
+            out.push_str("\x1b\\");
+        }
+        out
+    }
+
+    // Transmit `image` to a Sixel-compatible terminal.
+    fn draw_image_sixel(
+        &self,
+        image: &DynamicImage,
+        start_column: u16,
+        row: u16,
+        width_px: u32,
+        height_px: u32,
+    ) -> Result<(), RenderImageError> {
+        let resized = image.resize_exact(width_px.max(1), height_px.max(1), FilterType::Lanczos3);
+        let sequence = sixel::encode(&resized);
+
+        let mut stdout = io::stdout();
+        execute!(stdout, cursor::MoveTo(start_column, row))?;
+        stdout.write_all(sequence.as_bytes())?;
+        stdout.flush()?;
         Ok(())
     }
 
@@ -84,7 +325,9 @@ impl MediaRender {
         for entry in entries {
             let Ok(entry) = entry else { continue };
             let path = entry.path();
-            let Some(file_name) = path.file_name().and_then(|f| f.to_str()) else { continue };
+            let Some(file_name) = path.file_name().and_then(|f| f.to_str()) else {
+                continue;
+            };
             if file_name.starts_with(".tmp.viuer.") {
                 let _ = fs::remove_file(&path);
             }
@@ -92,6 +335,168 @@ impl MediaRender {
     }
 }
 
+// A minimal Sixel encoder. Sixel terminals don't support truecolor, so the image is first
+// quantized down to a fixed 6x6x6 RGB color cube before being encoded into the DCS sixel
+// sequence.
+mod sixel {
+    use super::DynamicImage;
+
+    const CUBE_LEVELS: u32 = 6;
+    const PALETTE_SIZE: u32 = CUBE_LEVELS * CUBE_LEVELS * CUBE_LEVELS;
+
+    /// Encode `image` into a DEC Sixel DCS escape sequence.
+    pub(super) fn encode(image: &DynamicImage) -> String {
+        let rgb = image.to_rgb8();
+        let (width, height) = rgb.dimensions();
+        let pixel_color = |x: u32, y: u32| -> u32 {
+            let pixel = rgb.get_pixel(x, y);
+            let (r, g, b) = (
+                quantize_channel(pixel[0]),
+                quantize_channel(pixel[1]),
+                quantize_channel(pixel[2]),
+            );
+            r * CUBE_LEVELS * CUBE_LEVELS + g * CUBE_LEVELS + b
+        };
+
+        let mut out = String::from("\x1bPq");
+        out.push_str(&format!("\"1;1;{width};{height}"));
+        for index in 0..PALETTE_SIZE {
+            let (r, g, b) = (
+                index / (CUBE_LEVELS * CUBE_LEVELS),
+                (index / CUBE_LEVELS) % CUBE_LEVELS,
+                index % CUBE_LEVELS,
+            );
+            out.push_str(&format!(
+                "#{index};2;{};{};{}",
+                level_to_percent(r),
+                level_to_percent(g),
+                level_to_percent(b)
+            ));
+        }
+
+        let mut band_start = 0;
+        while band_start < height {
+            let band_height = (height - band_start).min(6);
+            let mut colors_used = Vec::new();
+            for x in 0..width {
+                for y in band_start..band_start + band_height {
+                    let color = pixel_color(x, y);
+                    if !colors_used.contains(&color) {
+                        colors_used.push(color);
+                    }
+                }
+            }
+            for (i, &color) in colors_used.iter().enumerate() {
+                out.push_str(&format!("#{color}"));
+                for x in 0..width {
+                    let mut bits = 0u8;
+                    for row in 0..band_height {
+                        if pixel_color(x, band_start + row) == color {
+                            bits |= 1 << row;
+                        }
+                    }
+                    out.push((63 + bits) as char);
+                }
+                if i + 1 < colors_used.len() {
+                    out.push('$');
+                }
+            }
+            band_start += band_height;
+            if band_start < height {
+                out.push('-');
+            }
+        }
+        out.push_str("\x1b\\");
+        out
+    }
+
+    // Quantize a single 0-255 color channel down to one of `CUBE_LEVELS` levels.
+    fn quantize_channel(value: u8) -> u32 {
+        ((value as u32 * (CUBE_LEVELS - 1) + 127) / 255).min(CUBE_LEVELS - 1)
+    }
+
+    // Convert a 0..CUBE_LEVELS color level into the 0-100 percentage Sixel color registers use.
+    fn level_to_percent(level: u32) -> u32 {
+        (level * 100) / (CUBE_LEVELS - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded_of_len(len: usize) -> String {
+        "A".repeat(len)
+    }
+
+    #[test]
+    fn chunk_exactly_at_the_boundary_is_a_single_chunk() {
+        let encoded = encoded_of_len(KITTY_CHUNK_SIZE);
+        let sequence = MediaRender::build_kitty_chunks(32, 10, 10, &encoded);
+        assert_eq!(sequence.matches("\x1b_G").count(), 1);
+        assert!(sequence.starts_with("\x1b_Ga=T,f=32,s=10,v=10,m=0;"));
+        assert!(sequence.ends_with("\x1b\\"));
+    }
+
+    #[test]
+    fn one_byte_over_the_boundary_splits_into_two_chunks() {
+        let encoded = encoded_of_len(KITTY_CHUNK_SIZE + 1);
+        let sequence = MediaRender::build_kitty_chunks(32, 10, 10, &encoded);
+        assert_eq!(sequence.matches("\x1b_G").count(), 2);
+        assert!(sequence.starts_with("\x1b_Ga=T,f=32,s=10,v=10,m=1;"));
+        assert!(sequence.contains("\x1b_Gm=0;A\x1b\\"));
+    }
+
+    #[test]
+    fn one_byte_under_the_boundary_is_a_single_chunk() {
+        let encoded = encoded_of_len(KITTY_CHUNK_SIZE - 1);
+        let sequence = MediaRender::build_kitty_chunks(24, 10, 10, &encoded);
+        assert_eq!(sequence.matches("\x1b_G").count(), 1);
+        assert!(sequence.starts_with("\x1b_Ga=T,f=24,s=10,v=10,m=0;"));
+    }
+
+    #[test]
+    fn sixel_output_is_wrapped_in_the_dcs_sequence() {
+        let image = DynamicImage::new_rgb8(2, 2);
+        let sequence = sixel::encode(&image);
+        assert!(sequence.starts_with("\x1bPq\"1;1;2;2"));
+        assert!(sequence.ends_with("\x1b\\"));
+    }
+
+    #[test]
+    fn sixel_single_color_image_has_no_color_run_separators() {
+        let mut img = image::RgbImage::new(2, 2);
+        for pixel in img.pixels_mut() {
+            *pixel = image::Rgb([10, 20, 30]);
+        }
+        let sequence = sixel::encode(&DynamicImage::ImageRgb8(img));
+        // A single solid color only needs one color's worth of sixel data, so there should be no
+        // `$` run separator between colors.
+        assert!(!sequence.contains('$'));
+    }
+
+    #[test]
+    fn compute_rect_fits_as_is_and_is_centered() {
+        let dimensions = WindowSize { rows: 50, columns: 100, height: 800, width: 1000 };
+        let rect = MediaRender::compute_rect(200, 160, &dimensions, 0);
+        assert_eq!(rect, Rect { start_column: 40, start_row: 0, width: 20, height: 10 });
+    }
+
+    #[test]
+    fn compute_rect_shrinks_to_fit_the_available_height() {
+        let dimensions = WindowSize { rows: 10, columns: 100, height: 160, width: 1000 };
+        let rect = MediaRender::compute_rect(1000, 160, &dimensions, 5);
+        assert_eq!(rect, Rect { start_column: 25, start_row: 5, width: 50, height: 5 });
+    }
+
+    #[test]
+    fn compute_rect_is_capped_by_the_column_margin() {
+        let dimensions = WindowSize { rows: 50, columns: 100, height: 800, width: 1000 };
+        let rect = MediaRender::compute_rect(2000, 160, &dimensions, 0);
+        assert_eq!(rect, Rect { start_column: 3, start_row: 0, width: 95, height: 4 });
+    }
+}
+
 /// An invalid image.
 #[derive(thiserror::Error, Debug)]
 #[error("invalid image: {0}")]
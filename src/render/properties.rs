@@ -0,0 +1,81 @@
+use crossterm::terminal;
+use std::io;
+
+/// The cell size, in pixels, assumed when the terminal reports zero pixel dimensions. This is a
+/// common size for terminals running inside tmux or other multiplexers that answer the cell
+/// count but not the pixel size.
+const FALLBACK_CELL_WIDTH_PX: u16 = 8;
+const FALLBACK_CELL_HEIGHT_PX: u16 = 16;
+
+/// The terminal's window size, in both character cells and pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WindowSize {
+    /// The number of rows the terminal has.
+    pub rows: u16,
+
+    /// The number of columns the terminal has.
+    pub columns: u16,
+
+    /// The terminal's height, in pixels.
+    pub height: u16,
+
+    /// The terminal's width, in pixels.
+    pub width: u16,
+}
+
+impl WindowSize {
+    /// Query the terminal's current window size.
+    ///
+    /// This reads both cell and pixel dimensions in a single call to crossterm's
+    /// `terminal::window_size`; no in-band escape-sequence query is sent to the terminal. It's
+    /// not cached: terminals get resized mid-presentation, so every call re-reads
+    /// the live size rather than risk handing back a stale one from before a resize. Terminals
+    /// that report zero pixel dimensions fall back to an assumed cell size so callers never end up
+    /// dividing by zero or scaling images down to nothing.
+    pub fn current() -> io::Result<Self> {
+        let raw = terminal::window_size()?;
+        Ok(Self::from_raw(raw.rows, raw.columns, raw.width, raw.height))
+    }
+
+    fn from_raw(rows: u16, columns: u16, width: u16, height: u16) -> Self {
+        let width = if width == 0 { columns.saturating_mul(FALLBACK_CELL_WIDTH_PX) } else { width };
+        let height = if height == 0 { rows.saturating_mul(FALLBACK_CELL_HEIGHT_PX) } else { height };
+        Self { rows, columns, width, height }
+    }
+
+    /// The width, in pixels, of a single column.
+    pub fn pixels_per_column(&self) -> f64 {
+        self.width as f64 / self.columns.max(1) as f64
+    }
+
+    /// The height, in pixels, of a single row.
+    pub fn pixels_per_row(&self) -> f64 {
+        self.height as f64 / self.rows.max(1) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn degenerate_pixels_fall_back_to_assumed_cell_size() {
+        let size = WindowSize::from_raw(80, 200, 0, 0);
+        assert_eq!(size.width, 200 * FALLBACK_CELL_WIDTH_PX);
+        assert_eq!(size.height, 80 * FALLBACK_CELL_HEIGHT_PX);
+    }
+
+    #[test]
+    fn real_pixels_are_kept_as_is() {
+        let size = WindowSize::from_raw(80, 200, 1600, 800);
+        assert_eq!(size.width, 1600);
+        assert_eq!(size.height, 800);
+    }
+
+    #[test]
+    fn pixels_per_column_and_row_are_derived_from_the_final_dimensions() {
+        let size = WindowSize::from_raw(10, 20, 0, 0);
+        assert_eq!(size.pixels_per_column(), FALLBACK_CELL_WIDTH_PX as f64);
+        assert_eq!(size.pixels_per_row(), FALLBACK_CELL_HEIGHT_PX as f64);
+    }
+}
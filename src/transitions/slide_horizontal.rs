@@ -2,8 +2,10 @@ use super::{AnimateTransition, LinesFrame, TransitionDirection};
 use crate::{
     WindowSize,
     markdown::elements::Line,
+    render::media::Image,
     terminal::virt::{TerminalGrid, TerminalRowIterator},
 };
+use std::collections::HashMap;
 
 pub(crate) struct SlideHorizontalAnimation {
     grid: TerminalGrid,
@@ -16,14 +18,33 @@ impl SlideHorizontalAnimation {
         assert!(left.rows[0].len() == right.rows[0].len(), "different column count");
         assert!(left.background_color == right.background_color, "different background color");
 
+        // Images are placed by their column offset into the grid, so shift the right slide's
+        // images by however many columns the left slide occupies before merging them in.
+        let column_offset = left.rows[0].len();
         let mut rows = Vec::new();
         for (mut row, right) in left.rows.into_iter().zip(right.rows) {
             row.extend(right);
             rows.push(row);
         }
-        let grid = TerminalGrid { rows, background_color: left.background_color, images: Default::default() };
+
+        let mut images = left.images;
+        images.extend(right.images.into_iter().map(|(column, image)| (column + column_offset, image)));
+
+        let grid = TerminalGrid { rows, background_color: left.background_color, images };
         Self { grid, dimensions }
     }
+
+    // Find the images that fall within the visible `[index, index + columns)` window and shift
+    // their column back down to be relative to that window rather than to the whole merged grid.
+    fn visible_images(&self, index: usize) -> HashMap<usize, Image> {
+        let columns = self.dimensions.columns as usize;
+        self.grid
+            .images
+            .iter()
+            .filter(|(column, _)| (index..index + columns).contains(column))
+            .map(|(column, image)| (column - index, image.clone()))
+            .collect()
+    }
 }
 
 impl AnimateTransition for SlideHorizontalAnimation {
@@ -56,7 +77,8 @@ impl AnimateTransition for SlideHorizontalAnimation {
             }
             lines.push(Line(line));
         }
-        LinesFrame { lines, background_color: self.grid.background_color }
+        let images = self.visible_images(index);
+        LinesFrame { lines, background_color: self.grid.background_color, images }
     }
 
     fn total_frames(&self) -> usize {
@@ -99,4 +121,50 @@ mod tests {
         let lines: Vec<_> = transition.build_frame(frame, direction).lines.into_iter().map(as_text).collect();
         assert_eq!(lines, expected);
     }
+
+    fn test_image() -> Image {
+        #[rustfmt::skip]
+        const PNG_1X1: &[u8] = &[
+            0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+            0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f, 0x15, 0xc4,
+            0x89, 0x00, 0x00, 0x00, 0x0a, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0x00, 0x01, 0x00, 0x00,
+            0x05, 0x00, 0x01, 0x0d, 0x0a, 0x2d, 0xb4, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae,
+            0x42, 0x60, 0x82,
+        ];
+        Image::new(PNG_1X1).expect("valid image")
+    }
+
+    #[test]
+    fn images_are_merged_and_shifted() {
+        let mut left = build_grid(&["AB", "CD"]);
+        left.images.insert(0, test_image());
+
+        let mut right = build_grid(&["EF", "GH"]);
+        right.images.insert(1, test_image());
+
+        let transition = SlideHorizontalAnimation::new(left, right, WindowSize { rows: 2, columns: 2, height: 0, width: 0 });
+        let columns: Vec<_> = {
+            let mut columns: Vec<_> = transition.grid.images.keys().copied().collect();
+            columns.sort();
+            columns
+        };
+        assert_eq!(columns, &[0, 3]);
+    }
+
+    #[test]
+    fn visible_images_are_clipped_to_the_window() {
+        let mut left = build_grid(&["AB", "CD"]);
+        left.images.insert(0, test_image());
+
+        let mut right = build_grid(&["EF", "GH"]);
+        right.images.insert(1, test_image());
+
+        let transition = SlideHorizontalAnimation::new(left, right, WindowSize { rows: 2, columns: 2, height: 0, width: 0 });
+
+        let visible = transition.visible_images(0);
+        assert_eq!(visible.keys().copied().collect::<Vec<_>>(), &[0]);
+
+        let visible = transition.visible_images(3);
+        assert_eq!(visible.keys().copied().collect::<Vec<_>>(), &[0]);
+    }
 }